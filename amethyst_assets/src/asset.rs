@@ -6,6 +6,7 @@ use rayon::ThreadPool;
 use specs::{Component, DenseVecStorage};
 
 use {BoxedErr, SharedAssetError, StoreId};
+use loader::Loader;
 
 /// One of the three core traits of this crate.
 ///
@@ -182,8 +183,12 @@ pub trait Format {
     /// `IntoFuture`.
     type Result: IntoFuture<Item = Self::Data, Error = Self::Error>;
 
-
-
     /// Reads the given bytes and produces asset data.
-    fn parse(&self, bytes: Vec<u8>, pool: &ThreadPool) -> Self::Result;
+    ///
+    /// `loader` is passed in so a compound format can load the assets it
+    /// depends on (e.g. a mesh loading the textures it references) and fold
+    /// the resulting `AssetFuture`s into `Self::Result`, so that the future
+    /// returned here only completes once the whole dependency graph has
+    /// resolved.
+    fn parse(&self, bytes: Vec<u8>, pool: &ThreadPool, loader: &Loader) -> Self::Result;
 }
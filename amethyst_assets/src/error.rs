@@ -0,0 +1,40 @@
+use std::error::Error as StdError;
+
+use futures::{Future, IntoFuture};
+
+use BoxedErr;
+
+/// Shorthand for the common case of a `Format` or `Context` declaring
+/// `type Error = BoxedErr` and wanting to return `Result<T, BoxedErr>` from
+/// ordinary, `?`-using fallible code.
+pub type ParseResult<T> = Result<T, BoxedErr>;
+
+/// Adapts any `Result`-returning computation into one whose error has been
+/// boxed into a `BoxedErr`, so it can be used directly as a `Format::Result`
+/// or `Context::Result` without a bespoke error enum.
+///
+/// A format author writes their `parse` body against whatever concrete
+/// errors their decode steps produce (a decode error, then a sub-asset
+/// error, then an IO error) and calls `.into_boxed()` once at the end; the
+/// original error's source chain is preserved, and `BoxedErr::new` (which
+/// this delegates to) never boxes an already-boxed `BoxedErr` a second
+/// time.
+pub trait IntoBoxedFuture {
+    /// The value produced on success.
+    type Item;
+
+    /// Boxes this future's error into a `BoxedErr`.
+    fn into_boxed(self) -> Box<Future<Item = Self::Item, Error = BoxedErr>>;
+}
+
+impl<F> IntoBoxedFuture for F
+where
+    F: IntoFuture + 'static,
+    F::Error: StdError + Send + Sync + 'static,
+{
+    type Item = F::Item;
+
+    fn into_boxed(self) -> Box<Future<Item = Self::Item, Error = BoxedErr>> {
+        Box::new(self.into_future().map_err(BoxedErr::new))
+    }
+}
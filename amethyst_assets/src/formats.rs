@@ -0,0 +1,233 @@
+use std::marker::PhantomData;
+
+use futures::future::ok;
+use rayon::ThreadPool;
+use serde::de::DeserializeOwned;
+
+use {BoxedErr, Format, Loader};
+
+/// Supplies the `EXTENSIONS` a generic format is registered under.
+///
+/// `RonFormat<D>` and friends are parameterized over this (rather than
+/// hardcoding their extensions) so a user can register the same format
+/// under custom extensions just by supplying a different `Extension` type,
+/// without writing a new `Format` impl.
+pub trait Extension: Send + Sync + 'static {
+    /// The extensions (without `.`) a format using this marker is
+    /// registered under.
+    const EXTENSIONS: &'static [&'static str];
+}
+
+/// The default `RonFormat` extension: `["ron"]`.
+pub struct Ron;
+impl Extension for Ron {
+    const EXTENSIONS: &'static [&'static str] = &["ron"];
+}
+
+/// The default `JsonFormat` extension: `["json"]`.
+pub struct Json;
+impl Extension for Json {
+    const EXTENSIONS: &'static [&'static str] = &["json"];
+}
+
+/// The default `YamlFormat` extension: `["yaml"]`.
+pub struct Yaml;
+impl Extension for Yaml {
+    const EXTENSIONS: &'static [&'static str] = &["yaml"];
+}
+
+/// A `Format` that reads `Data` straight from RON, with no intermediate
+/// representation of its own.
+///
+/// Parameterized over the target type so it can be reused for any
+/// RON-encoded config/data asset without writing a bespoke `Format` impl.
+/// Registered under `E::EXTENSIONS`, which defaults to `["ron"]`; pass a
+/// different `Extension` type as `E` to register the same format under
+/// custom extensions.
+pub struct RonFormat<D, E = Ron>(PhantomData<(D, E)>);
+
+/// A `Format` that reads `Data` straight from JSON.
+///
+/// See `RonFormat` for the rationale; this is the same shape, decoding with
+/// `serde_json` instead. Defaults to the `["json"]` extension.
+pub struct JsonFormat<D, E = Json>(PhantomData<(D, E)>);
+
+/// A `Format` that reads `Data` straight from YAML.
+///
+/// See `RonFormat` for the rationale; this is the same shape, decoding with
+/// `serde_yaml` instead. Defaults to the `["yaml"]` extension.
+pub struct YamlFormat<D, E = Yaml>(PhantomData<(D, E)>);
+
+macro_rules! serde_format {
+    ($format:ident, $decode:expr) => {
+        impl<D, E> Default for $format<D, E> {
+            fn default() -> Self {
+                $format(PhantomData)
+            }
+        }
+
+        impl<D, E> Format for $format<D, E>
+        where
+            D: DeserializeOwned + Send + Sync + 'static,
+            E: Extension,
+        {
+            const EXTENSIONS: &'static [&'static str] = E::EXTENSIONS;
+            type Data = D;
+            type Error = BoxedErr;
+            type Result = Box<::futures::Future<Item = D, Error = BoxedErr>>;
+
+            fn parse(&self, bytes: Vec<u8>, _pool: &ThreadPool, _loader: &Loader) -> Self::Result {
+                let decode: fn(&[u8]) -> Result<D, BoxedErr> = $decode;
+
+                Box::new(ok(()).and_then(move |_| decode(&bytes)))
+            }
+        }
+    };
+}
+
+serde_format!(RonFormat, |bytes| {
+    ::ron::de::from_bytes(bytes).map_err(BoxedErr::new)
+});
+serde_format!(JsonFormat, |bytes| {
+    ::serde_json::from_slice(bytes).map_err(BoxedErr::new)
+});
+serde_format!(YamlFormat, |bytes| {
+    ::serde_yaml::from_slice(bytes).map_err(BoxedErr::new)
+});
+
+/// A marker `Extension` with no extensions of its own.
+///
+/// `PassthroughFormat`'s default: passthrough has no single conventional
+/// extension, so it is meant to be registered explicitly under whichever
+/// extension the caller's raw-data asset actually uses, by supplying a
+/// custom `Extension` as `E`.
+pub struct NoExtension;
+impl Extension for NoExtension {
+    const EXTENSIONS: &'static [&'static str] = &[];
+}
+
+/// A `Format` that performs no decoding at all, simply yielding the raw
+/// bytes it was given, converted via `Data: From<Vec<u8>>`.
+///
+/// Useful for `Data` types that already know how to build themselves from a
+/// `Vec<u8>` (e.g. a thin wrapper around compressed or otherwise
+/// self-describing data). Carries no extensions by default; see
+/// `NoExtension`.
+pub struct PassthroughFormat<D, E = NoExtension>(PhantomData<(D, E)>);
+
+impl<D, E> Default for PassthroughFormat<D, E> {
+    fn default() -> Self {
+        PassthroughFormat(PhantomData)
+    }
+}
+
+impl<D, E> Format for PassthroughFormat<D, E>
+where
+    D: From<Vec<u8>> + Send + Sync + 'static,
+    E: Extension,
+{
+    const EXTENSIONS: &'static [&'static str] = E::EXTENSIONS;
+    type Data = D;
+    type Error = BoxedErr;
+    type Result = Box<::futures::Future<Item = D, Error = BoxedErr>>;
+
+    fn parse(&self, bytes: Vec<u8>, _pool: &ThreadPool, _loader: &Loader) -> Self::Result {
+        Box::new(ok(D::from(bytes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use futures::{Future, IntoFuture};
+    use rayon::ThreadPoolBuilder;
+    use specs::Resources;
+
+    use StoreId;
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    fn test_loader() -> (Loader, Arc<ThreadPool>) {
+        let pool = Arc::new(ThreadPoolBuilder::new().build().unwrap());
+        let loader = Loader::new(
+            StoreId(0),
+            Arc::new(HashMap::new()),
+            Arc::new(Resources::new()),
+            pool.clone(),
+        );
+
+        (loader, pool)
+    }
+
+    #[test]
+    fn ron_format_decodes_its_data() {
+        let (loader, pool) = test_loader();
+        let fmt = RonFormat::<Point>::default();
+
+        let point = fmt.parse(b"(x: 1, y: 2)".to_vec(), &pool, &loader)
+            .into_future()
+            .wait()
+            .unwrap();
+
+        assert_eq!(point, Point { x: 1, y: 2 });
+    }
+
+    #[test]
+    fn json_format_decodes_its_data() {
+        let (loader, pool) = test_loader();
+        let fmt = JsonFormat::<Point>::default();
+
+        let point = fmt.parse(br#"{"x": 1, "y": 2}"#.to_vec(), &pool, &loader)
+            .into_future()
+            .wait()
+            .unwrap();
+
+        assert_eq!(point, Point { x: 1, y: 2 });
+    }
+
+    #[test]
+    fn yaml_format_decodes_its_data() {
+        let (loader, pool) = test_loader();
+        let fmt = YamlFormat::<Point>::default();
+
+        let point = fmt.parse(b"x: 1\ny: 2".to_vec(), &pool, &loader)
+            .into_future()
+            .wait()
+            .unwrap();
+
+        assert_eq!(point, Point { x: 1, y: 2 });
+    }
+
+    #[test]
+    fn ron_format_surfaces_decode_errors() {
+        let (loader, pool) = test_loader();
+        let fmt = RonFormat::<Point>::default();
+
+        let result = fmt.parse(b"not ron".to_vec(), &pool, &loader)
+            .into_future()
+            .wait();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn passthrough_format_yields_raw_bytes() {
+        let (loader, pool) = test_loader();
+        let fmt = PassthroughFormat::<Vec<u8>>::default();
+        let bytes = vec![1, 2, 3];
+
+        let data = fmt.parse(bytes.clone(), &pool, &loader)
+            .into_future()
+            .wait()
+            .unwrap();
+
+        assert_eq!(data, bytes);
+    }
+}
@@ -0,0 +1,128 @@
+//! Filesystem-driven hot reloading, gated behind the `hot-reload` feature so
+//! release builds that never watch the disk don't pay for a file watcher
+//! thread or its dependencies.
+#![cfg(feature = "hot-reload")]
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+
+use futures::{Future, IntoFuture};
+use notify::{DebouncedEvent, RecommendedWatcher, Watcher};
+use rayon::ThreadPool;
+use specs::Resources;
+
+use {Asset, AssetSpec, Context, Format, Loader, StoreId};
+
+/// A store that can be watched for changes, and that knows how to turn a
+/// modified path back into the `AssetSpec` it corresponds to.
+///
+/// This mirrors the category/name/extension convention a file-backed store
+/// already uses to resolve loads, just run in reverse.
+pub trait WatchedStore: Send + Sync + 'static {
+    /// The `StoreId` assets from this store are tagged with.
+    fn store_id(&self) -> StoreId;
+
+    /// The root directory to watch for modifications.
+    fn root(&self) -> &PathBuf;
+
+    /// Maps a path that changed somewhere under `root` back to the spec it
+    /// represents, if it corresponds to a known asset at all.
+    fn path_to_spec(&self, path: &PathBuf) -> Option<AssetSpec>;
+
+    /// Reads the current bytes at `path`.
+    fn read(&self, path: &PathBuf) -> Vec<u8>;
+}
+
+/// Watches a set of `WatchedStore`s and re-runs the appropriate `Format` on
+/// any asset whose backing file changes, handing the freshly parsed result
+/// to `Context::update`.
+///
+/// The file watcher debounces filesystem events on its own background
+/// thread, so a single save doesn't trigger several reparses; the reparsing
+/// itself happens only when `update` is called, on the calling thread. It
+/// only re-parses specs that are
+/// currently present in their context's cache (checked via `retrieve`) so
+/// files nobody has loaded yet are ignored.
+pub struct HotReloader {
+    _watcher: RecommendedWatcher,
+    events: Receiver<DebouncedEvent>,
+    stores: Vec<Box<WatchedStore>>,
+    pool: Arc<ThreadPool>,
+}
+
+impl HotReloader {
+    /// Creates a reloader watching every store in `stores`, debouncing
+    /// filesystem events over `debounce`.
+    pub fn new(
+        stores: Vec<Box<WatchedStore>>,
+        pool: Arc<ThreadPool>,
+        debounce: Duration,
+    ) -> Self {
+        let (tx, rx) = channel();
+        let mut watcher = Watcher::new(tx, debounce).expect("failed to start file watcher");
+
+        for store in &stores {
+            watcher
+                .watch(store.root(), ::notify::RecursiveMode::Recursive)
+                .expect("failed to watch asset store");
+        }
+
+        HotReloader {
+            _watcher: watcher,
+            events: rx,
+            stores,
+            pool,
+        }
+    }
+
+    /// Drains any pending filesystem events and re-parses the assets they
+    /// correspond to, updating their `Context`s in `res`.
+    ///
+    /// Intended to be polled once per frame from a `System`.
+    pub fn update<A, F>(&self, res: &Resources, format: &F, loader: &Loader)
+    where
+        A: Asset,
+        F: Format<Data = <A::Context as Context>::Data>,
+        F::Error: 'static,
+    {
+        let mut changed = HashSet::new();
+
+        while let Ok(event) = self.events.try_recv() {
+            // Editors that save via atomic create-and-rename are reported
+            // by `notify` as `Create`/`Rename`, not `Write`; treat all three
+            // as "the file at this path may have new content".
+            match event {
+                DebouncedEvent::Write(path) |
+                DebouncedEvent::Create(path) |
+                DebouncedEvent::Rename(_, path) => {
+                    changed.insert(path);
+                }
+                _ => {}
+            }
+        }
+
+        for path in &changed {
+            for store in &self.stores {
+                let spec = match store.path_to_spec(path) {
+                    Some(spec) => spec,
+                    None => continue,
+                };
+
+                let context = res.fetch::<A::Context>();
+                if context.retrieve(&spec).is_none() {
+                    // Nobody has asked for this asset, nothing to refresh.
+                    continue;
+                }
+
+                let bytes = store.read(path);
+                let future = format.parse(bytes, &self.pool, loader).into_future();
+                context.update(&spec, ::AssetFuture::from_future(
+                    future.map_err(::BoxedErr::new),
+                ));
+            }
+        }
+    }
+}
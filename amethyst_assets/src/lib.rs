@@ -0,0 +1,155 @@
+//! Asset loading and management.
+
+extern crate futures;
+extern crate rayon;
+extern crate ron;
+extern crate serde;
+#[cfg(test)]
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+extern crate serde_yaml;
+extern crate specs;
+
+#[cfg(feature = "hot-reload")]
+extern crate notify;
+
+use std::error::Error;
+use std::fmt;
+
+use futures::future::SharedError;
+
+mod asset;
+mod error;
+mod formats;
+#[cfg(feature = "hot-reload")]
+mod hot_reload;
+mod loader;
+mod mem;
+mod registry;
+
+pub use asset::{Asset, AssetFuture, AssetSpec, Context, Format};
+pub use error::{IntoBoxedFuture, ParseResult};
+pub use formats::{Extension, Json, JsonFormat, NoExtension, PassthroughFormat, Ron, RonFormat,
+                   Yaml, YamlFormat};
+#[cfg(feature = "hot-reload")]
+pub use hot_reload::{HotReloader, WatchedStore};
+pub use loader::{Loader, Store};
+pub use mem::{MemoryStore, TempEntry, TempMemoryStore, Temporary};
+pub use registry::{AssetRegistry, LoadStatus};
+
+/// Uniquely identifies a store (a filesystem directory, an archive, an
+/// in-memory map, ...) assets can be loaded from.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct StoreId(pub u16);
+
+/// A type-erased `Error + Send + Sync`, used as the `Error` associated type
+/// for `Format`/`Context` implementations that would otherwise need a
+/// bespoke error enum.
+pub struct BoxedErr(pub Box<Error + Send + Sync>);
+
+impl BoxedErr {
+    /// Boxes any `Error + Send + Sync` into a `BoxedErr`.
+    ///
+    /// If `err` is already a `BoxedErr`, it is unwrapped and returned as-is
+    /// rather than being boxed a second time.
+    pub fn new<E>(err: E) -> Self
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        let err: Box<Error + Send + Sync> = Box::new(err);
+
+        match err.downcast::<BoxedErr>() {
+            Ok(already_boxed) => *already_boxed,
+            Err(err) => BoxedErr(err),
+        }
+    }
+}
+
+impl fmt::Debug for BoxedErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for BoxedErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Error for BoxedErr {
+    fn description(&self) -> &str {
+        self.0.description()
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        self.0.cause()
+    }
+}
+
+/// The error produced when polling any clone of an `AssetFuture` whose
+/// shared underlying future has failed, including the first clone to see
+/// the failure.
+#[derive(Debug)]
+pub struct SharedAssetError(pub SharedError<BoxedErr>);
+
+impl From<SharedError<BoxedErr>> for SharedAssetError {
+    fn from(err: SharedError<BoxedErr>) -> Self {
+        SharedAssetError(err)
+    }
+}
+
+impl fmt::Display for SharedAssetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&*self.0, f)
+    }
+}
+
+impl Error for SharedAssetError {
+    fn description(&self) -> &str {
+        self.0.description()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use std::fmt;
+
+    use BoxedErr;
+
+    #[derive(Debug)]
+    struct OtherError;
+
+    impl fmt::Display for OtherError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "other error")
+        }
+    }
+
+    impl ::std::error::Error for OtherError {
+        fn description(&self) -> &str {
+            "other error"
+        }
+    }
+
+    #[test]
+    fn new_boxes_a_plain_error() {
+        let err = BoxedErr::new(OtherError);
+
+        assert_eq!(err.description(), "other error");
+    }
+
+    #[test]
+    fn new_does_not_double_box_a_boxed_err() {
+        let once = BoxedErr::new(OtherError);
+        let twice = BoxedErr::new(once);
+
+        // If `new` re-boxed, the inner box would hold a `BoxedErr` wrapping
+        // `OtherError` rather than `OtherError` itself; `description()`
+        // forwards either way, so the downcast is what tells them apart.
+        assert!(twice.0.downcast_ref::<OtherError>().is_some());
+        assert_eq!(twice.description(), "other error");
+    }
+}
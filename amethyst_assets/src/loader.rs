@@ -0,0 +1,137 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use futures::{Future, IntoFuture};
+use futures::future::lazy;
+use rayon::ThreadPool;
+use specs::Resources;
+
+use {Asset, AssetFuture, AssetSpec, BoxedErr, Context, Format, StoreId};
+
+/// A backing store a `Loader` can read raw asset bytes from.
+///
+/// Implemented by anything that can resolve an `AssetSpec` to bytes: a
+/// filesystem directory, an archive, a `MemoryStore`, and so on. A `Loader`
+/// is handed a registry of these, keyed by `StoreId`, so it never needs to
+/// know which kind of store backs the id it was given.
+pub trait Store: Send + Sync {
+    /// Reads the current bytes backing `spec`.
+    fn read(&self, spec: &AssetSpec) -> Vec<u8>;
+}
+
+/// Handle passed to `Format::parse`, letting a format issue further asset
+/// loads while it is decoding its own data.
+///
+/// Compound formats (glTF, a level file referencing prefabs, ...) use this
+/// to request the assets they depend on and fold the resulting
+/// `AssetFuture`s into the future they hand back from `parse` (e.g. with
+/// `futures::future::join_all`). Every load issued through a `Loader`,
+/// nested or not, goes through the same per-type `Context` cache keyed by
+/// `AssetSpec`, so a texture referenced by two meshes is only ever decoded
+/// once, and the `Shared` plumbing in `AssetFuture` naturally deduplicates
+/// concurrent requests for it.
+#[derive(Clone)]
+pub struct Loader {
+    pool: Arc<ThreadPool>,
+    res: Arc<Resources>,
+    store: StoreId,
+    stores: Arc<HashMap<StoreId, Box<Store>>>,
+    // Reserves a cache slot for a spec before its `Format::parse` runs, so
+    // that two concurrent first-time loads of the same spec race on this
+    // lock rather than each independently parsing and `Context::cache`ing
+    // a distinct future (see `load`).
+    pending: Arc<Mutex<HashMap<AssetSpec, Box<Any>>>>,
+}
+
+impl Loader {
+    /// Creates a new `Loader` which resolves names against `store` (looked
+    /// up in `stores`), fetches `Context`s out of `res`, and passes `pool`
+    /// to any `Format::parse` and `Context::create_asset` calls it triggers.
+    pub fn new(
+        store: StoreId,
+        stores: Arc<HashMap<StoreId, Box<Store>>>,
+        res: Arc<Resources>,
+        pool: Arc<ThreadPool>,
+    ) -> Self {
+        Loader {
+            pool,
+            res,
+            store,
+            stores,
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Requests an asset of type `A`, returning the cached future for `name`
+    /// if one already exists in `A`'s `Context`, or starting a new load with
+    /// `format` otherwise.
+    ///
+    /// The returned future resolves independently of whatever is currently
+    /// being parsed, so several of these can be combined into a single
+    /// `Self::Result` that only completes once every dependency has. Because
+    /// this takes no `&Resources` of its own, it can be called directly from
+    /// inside `Format::parse`, which only ever sees a `&Loader`.
+    ///
+    /// The whole "is it cached, and if not, reserve its slot" decision for a
+    /// given `AssetSpec` happens under a single lock, so two concurrent
+    /// first-time loads of the same spec always end up sharing the one
+    /// `AssetFuture` built by whichever of them wins the lock, instead of
+    /// each parsing independently and clobbering the other's cache entry.
+    pub fn load<A, F>(&self, name: String, format: F) -> AssetFuture<A>
+    where
+        A: Asset + 'static,
+        F: Format<Data = <A::Context as Context>::Data> + Send + Sync + 'static,
+    {
+        let spec = AssetSpec::new(name, F::EXTENSIONS, self.store);
+        let mut pending = self.pending.lock().unwrap();
+
+        if let Some(future) = pending.get(&spec).and_then(
+            |any| any.downcast_ref::<AssetFuture<A>>(),
+        )
+        {
+            return future.clone();
+        }
+
+        let context = self.res.fetch::<A::Context>();
+
+        if let Some(future) = context.retrieve(&spec) {
+            pending.insert(spec, Box::new(future.clone()));
+            return future;
+        }
+
+        let loader = self.clone();
+        let read_spec = spec.clone();
+        // Reading and parsing wait for the first poll, after the `pending`
+        // lock has been released, so `parse` can itself call `load`.
+        let asset = AssetFuture::from_future(lazy(move || {
+            let bytes = loader.read(&read_spec);
+            let pool = loader.pool.clone();
+            let parsed = format
+                .parse(bytes, &pool, &loader)
+                .into_future()
+                .map_err(BoxedErr::new);
+
+            parsed.and_then(move |data| {
+                let context = loader.res.fetch::<A::Context>();
+
+                context.create_asset(data, &pool).into_future().map_err(
+                    BoxedErr::new,
+                )
+            })
+        }));
+
+        context.cache(spec.clone(), asset.clone());
+        pending.insert(spec, Box::new(asset.clone()));
+
+        asset
+    }
+
+    /// Reads the raw bytes backing `spec` from this loader's store.
+    fn read(&self, spec: &AssetSpec) -> Vec<u8> {
+        self.stores
+            .get(&self.store)
+            .unwrap_or_else(|| panic!("Loader configured with unknown store {:?}", self.store))
+            .read(spec)
+    }
+}
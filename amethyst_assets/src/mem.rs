@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+use {AssetSpec, StoreId};
+use loader::Store;
+
+/// A store backed by an in-memory `name -> bytes` map rather than a
+/// filesystem or archive.
+///
+/// Useful for procedurally generated content, test fixtures, and assets
+/// fetched at runtime (e.g. downloaded over the network), all of which
+/// participate in the usual `AssetSpec`/`Context` cache flow exactly like a
+/// file-backed store would. Implementing `Store` is what plugs it into that
+/// flow: register it in a `Loader`'s store registry under its `id()` and
+/// `Format::parse` runs over these bytes the same way it runs over bytes
+/// read from disk.
+pub struct MemoryStore {
+    id: StoreId,
+    entries: Arc<RwLock<HashMap<String, Vec<u8>>>>,
+}
+
+impl MemoryStore {
+    /// Creates a new, empty `MemoryStore` identified by `id`.
+    pub fn new(id: StoreId) -> Self {
+        MemoryStore {
+            id,
+            entries: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// The `StoreId` assets loaded from this store are tagged with.
+    pub fn id(&self) -> StoreId {
+        self.id
+    }
+
+    /// Registers `bytes` under `name`, overwriting any previous entry.
+    pub fn insert(&self, name: String, bytes: Vec<u8>) {
+        self.entries.write().unwrap().insert(name, bytes);
+    }
+
+    /// Removes the entry registered under `name`, if any.
+    pub fn remove(&self, name: &str) -> Option<Vec<u8>> {
+        self.entries.write().unwrap().remove(name)
+    }
+
+    /// Returns the bytes registered under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<Vec<u8>> {
+        self.entries.read().unwrap().get(name).cloned()
+    }
+}
+
+impl Store for MemoryStore {
+    fn read(&self, spec: &AssetSpec) -> Vec<u8> {
+        self.get(&spec.name).unwrap_or_default()
+    }
+}
+
+/// A `MemoryStore` whose entries are meant to be short-lived.
+///
+/// `insert` hands back a `TempEntry` guard rather than storing the bytes
+/// indefinitely; the entry is removed from the map as soon as that guard is
+/// dropped. Bundle it into the decoded `Data`/`Asset` via `Temporary` so it
+/// rides along inside the cached `AssetFuture` and the raw bytes are
+/// dropped from the map the moment the asset itself (and its cache entry)
+/// is released, instead of leaking across frames.
+///
+/// Each `insert` tags its entry with a fresh version number, and `TempEntry`
+/// is not `Clone`: there is exactly one guard per entry, so there is never a
+/// question of which of several clones is "the last one" (no strong-count
+/// race), and an entry re-inserted under a reused name gets a new version,
+/// so a stale guard from the previous insert can never evict it — its drop
+/// only removes the map entry if the stored version still matches its own.
+pub struct TempMemoryStore {
+    id: StoreId,
+    entries: Arc<RwLock<HashMap<String, (usize, Vec<u8>)>>>,
+    next_version: Arc<AtomicUsize>,
+}
+
+impl TempMemoryStore {
+    /// Creates a new, empty `TempMemoryStore` identified by `id`.
+    pub fn new(id: StoreId) -> Self {
+        TempMemoryStore {
+            id,
+            entries: Arc::new(RwLock::new(HashMap::new())),
+            next_version: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// The `StoreId` assets loaded from this store are tagged with.
+    pub fn id(&self) -> StoreId {
+        self.id
+    }
+
+    /// Registers `bytes` under `name` and returns a guard that removes the
+    /// entry again once it is dropped, unless `name` has since been
+    /// re-`insert`ed (in which case the newer entry is left alone).
+    pub fn insert(&self, name: String, bytes: Vec<u8>) -> TempEntry {
+        let version = self.next_version.fetch_add(1, Ordering::SeqCst);
+        self.entries.write().unwrap().insert(
+            name.clone(),
+            (version, bytes),
+        );
+
+        TempEntry {
+            entries: self.entries.clone(),
+            name,
+            version,
+        }
+    }
+}
+
+impl Store for TempMemoryStore {
+    fn read(&self, spec: &AssetSpec) -> Vec<u8> {
+        self.entries
+            .read()
+            .unwrap()
+            .get(&spec.name)
+            .map(|&(_, ref bytes)| bytes.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// RAII guard returned by `TempMemoryStore::insert`.
+///
+/// Removes its entry from the owning `TempMemoryStore` once dropped, unless
+/// a newer `insert` under the same name has already replaced it. The check
+/// and the removal happen under the same write-lock acquisition, so two
+/// guards can never race on the same entry the way a bare strong-count
+/// check across clones would.
+pub struct TempEntry {
+    entries: Arc<RwLock<HashMap<String, (usize, Vec<u8>)>>>,
+    name: String,
+    version: usize,
+}
+
+impl Drop for TempEntry {
+    fn drop(&mut self) {
+        let mut entries = self.entries.write().unwrap();
+
+        let is_current = entries.get(&self.name).map(|&(version, _)| version) ==
+            Some(self.version);
+
+        if is_current {
+            entries.remove(&self.name);
+        }
+    }
+}
+
+/// Bundles a value with a `TempEntry` guard, so the guard's lifetime (and
+/// thus the lifetime of the backing `TempMemoryStore` entry) is tied to the
+/// value's.
+///
+/// Wrap a `Format`'s decoded `Data` (or the final `Asset`) in this before
+/// handing it back from a `TempMemoryStore`-backed load, so the raw bytes
+/// are released as soon as the asset is.
+pub struct Temporary<T> {
+    value: T,
+    _entry: TempEntry,
+}
+
+impl<T> Temporary<T> {
+    /// Wraps `value`, keeping `entry` alive for as long as this is.
+    pub fn new(value: T, entry: TempEntry) -> Self {
+        Temporary {
+            value,
+            _entry: entry,
+        }
+    }
+
+    /// Unwraps the value, dropping the guard that kept its backing entry
+    /// alive.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for Temporary<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Temporary<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dropping_the_guard_removes_the_entry() {
+        let store = TempMemoryStore::new(StoreId(0));
+        let guard = store.insert("foo".to_owned(), vec![1, 2, 3]);
+
+        assert_eq!(store.entries.read().unwrap().len(), 1);
+
+        drop(guard);
+
+        assert!(store.entries.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn a_stale_guard_does_not_evict_a_newer_insert() {
+        let store = TempMemoryStore::new(StoreId(0));
+        let first = store.insert("foo".to_owned(), vec![1]);
+
+        // Re-insert under the same name before the first guard is dropped;
+        // this bumps the version, so `first` is now stale.
+        let _second = store.insert("foo".to_owned(), vec![2]);
+
+        drop(first);
+
+        // The stale guard must not have removed the newer entry.
+        let entries = store.entries.read().unwrap();
+        assert_eq!(entries.get("foo").map(|&(_, ref bytes)| bytes.clone()), Some(vec![2]));
+    }
+
+    #[test]
+    fn read_reflects_the_current_entry() {
+        let store = TempMemoryStore::new(StoreId(0));
+        let _guard = store.insert("foo".to_owned(), vec![9, 9]);
+
+        let spec = AssetSpec::new("foo".to_owned(), &[], StoreId(0));
+        assert_eq!(store.read(&spec), vec![9, 9]);
+    }
+}
@@ -0,0 +1,83 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use {Asset, AssetFuture, AssetSpec};
+
+/// Whether a registered asset has finished loading yet.
+///
+/// Derived from `AssetFuture::peek`, so checking it never blocks or
+/// triggers the work the future represents.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoadStatus {
+    /// The asset is still being loaded (or hasn't started polling yet).
+    Loading,
+    /// The asset finished loading successfully.
+    Loaded,
+    /// The asset failed to load.
+    Failed,
+}
+
+/// Tracks every `AssetSpec` a `Context` has been asked to load, independent
+/// of any single `Context`'s own private cache.
+///
+/// This lets tooling (editor panels, debug overlays) enumerate the set of
+/// currently known assets and their load state, and lets one subsystem
+/// fetch an asset another subsystem already loaded purely from its
+/// `AssetSpec`, without kicking off a duplicate load.
+///
+/// `AssetFuture`'s inner `Shared<Box<Future<..>>>` is not `Send`/`Sync` (its
+/// boxed future has no such bound), so this registry isn't either; it's
+/// meant for same-thread tooling such as an editor overlay, not for sharing
+/// an in-flight load across threads.
+#[derive(Default)]
+pub struct AssetRegistry {
+    entries: RefCell<HashMap<AssetSpec, Box<Any>>>,
+}
+
+impl AssetRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        AssetRegistry { entries: RefCell::new(HashMap::new()) }
+    }
+
+    /// Records that `future` is the `AssetFuture` for `spec`.
+    ///
+    /// Call this from `Context::cache` so every load, regardless of which
+    /// subsystem triggered it, becomes visible here.
+    pub fn register<A: Asset>(&self, spec: AssetSpec, future: AssetFuture<A>)
+    where
+        A: 'static,
+    {
+        self.entries.borrow_mut().insert(spec, Box::new(future));
+    }
+
+    /// Returns the `AssetFuture<A>` registered for `spec`, if any, without
+    /// starting a new load.
+    pub fn get<A>(&self, spec: &AssetSpec) -> Option<AssetFuture<A>>
+    where
+        A: 'static,
+    {
+        self.entries.borrow().get(spec).and_then(|any| {
+            any.downcast_ref::<AssetFuture<A>>().cloned()
+        })
+    }
+
+    /// Returns the load status of `spec` without blocking or triggering
+    /// work, or `None` if no asset has been registered for it.
+    pub fn status<A>(&self, spec: &AssetSpec) -> Option<LoadStatus>
+    where
+        A: Clone + 'static,
+    {
+        self.get::<A>(spec).map(|future| match future.peek() {
+            None => LoadStatus::Loading,
+            Some(Ok(_)) => LoadStatus::Loaded,
+            Some(Err(_)) => LoadStatus::Failed,
+        })
+    }
+
+    /// Iterates over every `AssetSpec` currently known to this registry.
+    pub fn specs(&self) -> Vec<AssetSpec> {
+        self.entries.borrow().keys().cloned().collect()
+    }
+}